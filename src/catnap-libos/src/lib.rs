@@ -185,9 +185,15 @@ fn catnap_listen(fd: c_int, backlog: c_int) -> c_int {
 //==============================================================================
 
 fn catnap_accept(qtok_out: *mut dmtr_qtoken_t, sockqd: c_int) -> c_int {
-    with_libos(|libos| {
-        unsafe { *qtok_out = libos.accept(sockqd as FileDescriptor).unwrap() };
-        0
+    with_libos(|libos| match libos.accept(sockqd as FileDescriptor) {
+        Ok(qt) => {
+            unsafe { *qtok_out = qt };
+            0
+        },
+        Err(e) => {
+            eprintln!("dmtr_accept failed: {:?}", e);
+            e.errno()
+        },
     })
 }
 
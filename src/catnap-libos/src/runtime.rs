@@ -12,6 +12,7 @@ use catnip::{
     protocols::{
         arp,
         ethernet2::{
+            frame::ETHERNET2_HEADER_SIZE,
             Ethernet2Header,
             MacAddress,
         },
@@ -34,6 +35,7 @@ use catnip::{
         WaitFuture,
     },
 };
+use demikernel::timer;
 use futures::{
     Future,
     FutureExt,
@@ -78,6 +80,9 @@ use std::{
 
 // ETH_P_ALL must be converted to big-endian short but (due to a bug in Rust libc bindings) comes as an int.
 const ETH_P_ALL: libc::c_ushort = (libc::ETH_P_ALL as libc::c_ushort).to_be();
+// Large enough for an Ethernet frame carrying the biggest possible IPv4 packet, which is what a
+// GRO-enabled interface can hand us. Anything bigger is detected and dropped in `receive()`.
+const RECEIVE_BUFFER_SIZE: usize = ETHERNET2_HEADER_SIZE + u16::MAX as usize;
 enum SockAddrPurpose {
     Bind,
     Send,
@@ -93,6 +98,7 @@ pub struct Inner {
     pub timer: TimerRc,
     pub rng: SmallRng,
     pub socket: Socket,
+    pub recv_buf: Box<[MaybeUninit<u8>]>,
     pub ifindex: i32,
     pub link_addr: MacAddress,
     pub ipv4_addr: Ipv4Addr,
@@ -155,6 +161,7 @@ impl LinuxRuntime {
             timer: TimerRc(Rc::new(Timer::new(now))),
             rng: SmallRng::from_seed([0; 32]),
             socket,
+            recv_buf: Box::new_uninit_slice(RECEIVE_BUFFER_SIZE),
             ifindex,
             link_addr,
             ipv4_addr,
@@ -266,20 +273,28 @@ impl Runtime for LinuxRuntime {
     }
 
     fn receive(&self) -> ArrayVec<Bytes, RECEIVE_BATCH_SIZE> {
-        // 4096B buffer size chosen arbitrarily, seems fine for now.
-        // This use-case is an example for MaybeUninit in the docs
-        let mut out: [MaybeUninit<u8>; 4096] =
-            [unsafe { MaybeUninit::uninit().assume_init() }; 4096];
-        if let Ok((bytes_read, _origin_addr)) = self.inner.borrow().socket.recv_from(&mut out[..]) {
-            let mut ret = ArrayVec::new();
-            unsafe {
-                let out = mem::transmute::<[MaybeUninit<u8>; 4096], [u8; 4096]>(out);
-                ret.push(BytesMut::from(&out[..bytes_read]).freeze());
-            }
-            ret
-        } else {
-            ArrayVec::new()
+        let mut ret = ArrayVec::new();
+        let inner = &mut *self.inner.borrow_mut();
+        // With MSG_TRUNC, packet sockets report the real frame length even if it didn't fit.
+        let frame_len = match inner
+            .socket
+            .recv_with_flags(&mut inner.recv_buf[..], libc::MSG_TRUNC)
+        {
+            Ok(frame_len) => frame_len,
+            Err(..) => return ret,
+        };
+        // Drop runt frames that can't even hold an Ethernet header.
+        if frame_len < ETHERNET2_HEADER_SIZE {
+            return ret;
+        }
+        // Drop truncated frames rather than handing the stack a cut-short packet.
+        if frame_len > RECEIVE_BUFFER_SIZE {
+            return ret;
         }
+        let frame =
+            unsafe { slice::from_raw_parts(inner.recv_buf.as_ptr() as *const u8, frame_len) };
+        ret.push(BytesMut::from(frame).freeze());
+        ret
     }
 
     fn scheduler(&self) -> &Scheduler<Operation<Self>> {
@@ -307,7 +322,7 @@ impl Runtime for LinuxRuntime {
     }
 
     fn advance_clock(&self, now: Instant) {
-        self.inner.borrow_mut().timer.0.advance_clock(now);
+        timer::advance_clock(&self.inner.borrow_mut().timer.0, now);
     }
 
     fn wait(&self, duration: Duration) -> Self::WaitFuture {
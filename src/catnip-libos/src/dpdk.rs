@@ -10,7 +10,10 @@ use anyhow::{
     format_err,
     Error,
 };
-use catnip::protocols::ethernet2::MacAddress;
+use catnip::protocols::ethernet2::{
+    frame::ETHERNET2_HEADER_SIZE,
+    MacAddress,
+};
 use dpdk_rs::{
     rte_delay_us_block,
     rte_eal_init,
@@ -43,6 +46,7 @@ use dpdk_rs::{
     ETH_LINK_FULL_DUPLEX,
     ETH_LINK_UP,
     ETH_RSS_IP,
+    RTE_ETHER_CRC_LEN,
     RTE_ETHER_MAX_JUMBO_FRAME_LEN,
     RTE_ETHER_MAX_LEN,
     RTE_ETH_DEV_NO_OWNER,
@@ -56,6 +60,9 @@ use std::{
     time::Duration,
 };
 
+/// Smallest MTU every IPv4 host must accept (RFC 791).
+const MIN_MTU: u16 = 576;
+
 macro_rules! expect_zero {
     ($name:ident ( $($arg: expr),* $(,)* )) => {{
         let ret = $name($($arg),*);
@@ -137,6 +144,8 @@ pub fn initialize_dpdk(
         memory_manager,
         arp_table,
         disable_arp,
+        use_jumbo_frames,
+        mtu,
         mss,
         tcp_checksum_offload,
         udp_checksum_offload,
@@ -173,21 +182,10 @@ fn initialize_dpdk_port(
     };
 
     println!("dev_info: {:?}", dev_info);
-    unsafe {
-        expect_zero!(rte_eth_dev_set_mtu(port_id, mtu))?;
-        let mut dpdk_mtu = 0u16;
-        expect_zero!(rte_eth_dev_get_mtu(port_id, &mut dpdk_mtu as *mut _))?;
-        if dpdk_mtu != mtu {
-            bail!("Failed to set MTU to {}, got back {}", mtu, dpdk_mtu);
-        }
-    }
+    set_port_mtu(port_id, mtu, use_jumbo_frames)?;
 
     let mut port_conf: rte_eth_conf = unsafe { MaybeUninit::zeroed().assume_init() };
-    port_conf.rxmode.max_rx_pkt_len = if use_jumbo_frames {
-        RTE_ETHER_MAX_JUMBO_FRAME_LEN
-    } else {
-        RTE_ETHER_MAX_LEN
-    };
+    port_conf.rxmode.max_rx_pkt_len = max_rx_pkt_len(use_jumbo_frames);
     if tcp_checksum_offload {
         port_conf.rxmode.offloads |= DEV_RX_OFFLOAD_TCP_CKSUM as u64;
     }
@@ -290,3 +288,70 @@ fn initialize_dpdk_port(
 
     Ok(())
 }
+
+/// Largest frame the port is configured to receive.
+fn max_rx_pkt_len(use_jumbo_frames: bool) -> u32 {
+    if use_jumbo_frames {
+        RTE_ETHER_MAX_JUMBO_FRAME_LEN
+    } else {
+        RTE_ETHER_MAX_LEN
+    }
+}
+
+/// Checks that `mtu` is a valid IPv4 MTU and that full-size frames fit in what the port is
+/// configured to receive.
+fn check_mtu(mtu: u16, use_jumbo_frames: bool) -> Result<(), Error> {
+    if mtu < MIN_MTU {
+        bail!("MTU {} is below the IPv4 minimum of {}", mtu, MIN_MTU);
+    }
+    // Like DPDK itself, count the CRC when comparing against max_rx_pkt_len.
+    let frame_size = mtu as usize + ETHERNET2_HEADER_SIZE + RTE_ETHER_CRC_LEN as usize;
+    let max_frame_size = max_rx_pkt_len(use_jumbo_frames) as usize;
+    if frame_size > max_frame_size {
+        bail!(
+            "MTU {} needs {}B frames, but the port receives at most {}B",
+            mtu,
+            frame_size,
+            max_frame_size
+        );
+    }
+    Ok(())
+}
+
+/// Sets the MTU of a DPDK port and checks that the device actually applied it.
+pub fn set_port_mtu(port_id: u16, mtu: u16, use_jumbo_frames: bool) -> Result<(), Error> {
+    check_mtu(mtu, use_jumbo_frames)?;
+    unsafe {
+        expect_zero!(rte_eth_dev_set_mtu(port_id, mtu))?;
+        let mut dpdk_mtu = 0u16;
+        expect_zero!(rte_eth_dev_get_mtu(port_id, &mut dpdk_mtu as *mut _))?;
+        if dpdk_mtu != mtu {
+            bail!("Failed to set MTU to {}, got back {}", mtu, dpdk_mtu);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_mtu_rejects_below_ipv4_minimum() {
+        assert!(check_mtu(MIN_MTU - 1, false).is_err());
+        assert!(check_mtu(MIN_MTU, false).is_ok());
+    }
+
+    #[test]
+    fn check_mtu_counts_header_and_crc() {
+        assert!(check_mtu(1500, false).is_ok());
+        assert!(check_mtu(1501, false).is_err());
+        assert!(check_mtu(1504, false).is_err());
+    }
+
+    #[test]
+    fn check_mtu_allows_jumbo_only_when_enabled() {
+        assert!(check_mtu(9000, false).is_err());
+        assert!(check_mtu(9000, true).is_ok());
+    }
+}
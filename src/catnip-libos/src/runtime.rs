@@ -1,8 +1,12 @@
-use crate::memory::{
-    DPDKBuf,
-    Mbuf,
-    MemoryManager,
+use crate::{
+    dpdk,
+    memory::{
+        DPDKBuf,
+        Mbuf,
+        MemoryManager,
+    },
 };
+use anyhow::Error;
 use arrayvec::ArrayVec;
 use catnip::{
     self,
@@ -10,10 +14,17 @@ use catnip::{
     protocols::{
         arp,
         ethernet2::{
-            frame::MIN_PAYLOAD_SIZE,
+            frame::{
+                ETHERNET2_HEADER_SIZE,
+                MIN_PAYLOAD_SIZE,
+            },
             MacAddress,
         },
-        tcp,
+        ipv4::datagram::IPV4_HEADER_SIZE,
+        tcp::{
+            self,
+            segment::MIN_TCP_HEADER_SIZE,
+        },
         udp,
     },
     runtime::{
@@ -32,6 +43,7 @@ use catnip::{
         WaitFuture,
     },
 };
+use demikernel::timer;
 use dpdk_rs::{
     rte_eth_rx_burst,
     rte_eth_tx_burst,
@@ -85,6 +97,8 @@ impl DPDKRuntime {
         memory_manager: MemoryManager,
         arp_table: HashMap<Ipv4Addr, MacAddress>,
         disable_arp: bool,
+        use_jumbo_frames: bool,
+        mtu: u16,
         mss: usize,
         tcp_checksum_offload: bool,
         udp_checksum_offload: bool,
@@ -101,18 +115,7 @@ impl DPDKRuntime {
             disable_arp,
         );
 
-        let tcp_options = tcp::Options::new(
-            Some(mss),
-            None,
-            None,
-            None,
-            None,
-            Some(0xffff),
-            Some(0),
-            None,
-            Some(tcp_checksum_offload),
-            Some(tcp_checksum_offload),
-        );
+        let tcp_options = tcp_options(mss, tcp_checksum_offload);
 
         let udp_options = udp::Options::new(udp_checksum_offload, udp_checksum_offload);
 
@@ -124,8 +127,11 @@ impl DPDKRuntime {
             arp_options,
             tcp_options,
             udp_options,
+            tcp_checksum_offload,
 
             dpdk_port_id,
+            use_jumbo_frames,
+            mtu,
             memory_manager,
         };
         Self {
@@ -145,6 +151,22 @@ impl DPDKRuntime {
     pub fn memory_manager(&self) -> MemoryManager {
         self.inner.borrow().memory_manager.clone()
     }
+
+    pub fn mtu(&self) -> u16 {
+        self.inner.borrow().mtu
+    }
+
+    /// Changes the MTU of the underlying port and replaces the MSS in the options returned by
+    /// `tcp_options()` with one derived from the new MTU. This does not renegotiate the MSS of
+    /// established connections; whether a connection sees the new value depends on when catnip
+    /// reads `tcp_options()`.
+    pub fn set_mtu(&self, mtu: u16) -> Result<(), Error> {
+        let mut inner = self.inner.borrow_mut();
+        dpdk::set_port_mtu(inner.dpdk_port_id, mtu, inner.use_jumbo_frames)?;
+        inner.tcp_options = tcp_options(mss_for_mtu(mtu), inner.tcp_checksum_offload);
+        inner.mtu = mtu;
+        Ok(())
+    }
 }
 
 struct Inner {
@@ -156,8 +178,31 @@ struct Inner {
     arp_options: arp::Options,
     tcp_options: tcp::Options<DPDKRuntime>,
     udp_options: udp::Options,
+    tcp_checksum_offload: bool,
 
     dpdk_port_id: u16,
+    use_jumbo_frames: bool,
+    mtu: u16,
+}
+
+/// MSS that fits a TCP segment without options into `mtu`.
+fn mss_for_mtu(mtu: u16) -> usize {
+    mtu as usize - IPV4_HEADER_SIZE - MIN_TCP_HEADER_SIZE
+}
+
+fn tcp_options(mss: usize, tcp_checksum_offload: bool) -> tcp::Options<DPDKRuntime> {
+    tcp::Options::new(
+        Some(mss),
+        None,
+        None,
+        None,
+        None,
+        Some(0xffff),
+        Some(0),
+        None,
+        Some(tcp_checksum_offload),
+        Some(tcp_checksum_offload),
+    )
 }
 
 impl Runtime for DPDKRuntime {
@@ -296,6 +341,10 @@ impl Runtime for DPDKRuntime {
                 ptr: packet,
                 mm: inner.memory_manager.clone(),
             };
+            // Drop runt frames that can't even hold an Ethernet header.
+            if mbuf.len() < ETHERNET2_HEADER_SIZE {
+                continue;
+            }
             out.push(DPDKBuf::Managed(mbuf));
         }
         out
@@ -322,7 +371,7 @@ impl Runtime for DPDKRuntime {
     }
 
     fn advance_clock(&self, now: Instant) {
-        self.inner.borrow_mut().timer.0.advance_clock(now);
+        timer::advance_clock(&self.inner.borrow_mut().timer.0, now);
     }
 
     fn wait(&self, duration: Duration) -> Self::WaitFuture {
@@ -365,3 +414,20 @@ impl Runtime for DPDKRuntime {
         &self.scheduler
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mss_for_mtu_subtracts_headers() {
+        assert_eq!(mss_for_mtu(576), 536);
+        assert_eq!(mss_for_mtu(1500), 1460);
+    }
+
+    #[test]
+    fn raising_mtu_raises_mss() {
+        assert_eq!(mss_for_mtu(9000), 8960);
+        assert!(mss_for_mtu(9000) > mss_for_mtu(1500));
+    }
+}
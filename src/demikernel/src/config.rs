@@ -63,6 +63,9 @@ impl Config {
                     .ok_or_else(|| format_err!("Couldn't find ARP table link_addr in config"))
                     .unwrap();
                 let link_addr = MacAddress::parse_str(link_addr_str).unwrap();
+                if link_addr.is_nil() || !link_addr.is_unicast() {
+                    panic!("Invalid link address in ARP table: {}", link_addr_str);
+                }
                 let ipv4_addr: Ipv4Addr = v
                     .as_str()
                     .ok_or_else(|| format_err!("Couldn't find ARP table link_addr in config"))
@@ -192,3 +195,55 @@ impl Config {
         Ok(Self::new(config_path))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_arp_entry(link_addr: &str) -> Config {
+        let yaml = format!(
+            "catnip:\n  arp_table:\n    \"{}\": \"192.168.1.2\"\n",
+            link_addr
+        );
+        let config_obj = YamlLoader::load_from_str(&yaml).unwrap().remove(0);
+        Config {
+            buffer_size: 64,
+            config_obj,
+            mtu: 1500,
+            mss: 1460,
+            disable_arp: false,
+            use_jumbo_frames: false,
+            udp_checksum_offload: false,
+            tcp_checksum_offload: false,
+            local_ipv4_addr: Ipv4Addr::new(192, 168, 1, 1),
+            local_link_addr: MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            local_interface_name: String::from("eth0"),
+        }
+    }
+
+    #[test]
+    fn arp_table_accepts_unicast_entry() {
+        let arp_table = config_with_arp_entry("02:00:00:00:00:02").arp_table();
+        let link_addr = MacAddress::new([0x02, 0x00, 0x00, 0x00, 0x00, 0x02]);
+        assert_eq!(arp_table.len(), 1);
+        assert_eq!(arp_table[&Ipv4Addr::new(192, 168, 1, 2)], link_addr);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid link address in ARP table")]
+    fn arp_table_rejects_broadcast_entry() {
+        config_with_arp_entry("ff:ff:ff:ff:ff:ff").arp_table();
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid link address in ARP table")]
+    fn arp_table_rejects_multicast_entry() {
+        config_with_arp_entry("01:00:5e:00:00:01").arp_table();
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid link address in ARP table")]
+    fn arp_table_rejects_nil_entry() {
+        config_with_arp_entry("00:00:00:00:00:00").arp_table();
+    }
+}
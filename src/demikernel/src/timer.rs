@@ -0,0 +1,52 @@
+// Copyright (c) Microsoft Corporation.
+// Licensed under the MIT license.
+
+use catnip::timer::{
+    Timer,
+    TimerPtr,
+};
+use std::time::Instant;
+
+//==============================================================================
+// Standalone Functions
+//==============================================================================
+
+/// Advances `timer` to `now`, unless `now` lies in the past. A stale `now` (e.g. from an embedder
+/// clock that stepped backwards) is treated as no time having passed, so the timer stays monotonic.
+pub fn advance_clock<P: TimerPtr>(timer: &Timer<P>, now: Instant) {
+    if now < timer.now() {
+        return;
+    }
+    timer.advance_clock(now);
+}
+
+//==============================================================================
+// Unit Tests
+//==============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use catnip::timer::TimerRc;
+    use std::{
+        rc::Rc,
+        time::Duration,
+    };
+
+    #[test]
+    fn advance_clock_moves_forward() {
+        let start = Instant::now();
+        let timer = TimerRc(Rc::new(Timer::new(start)));
+        advance_clock(&timer.0, start + Duration::from_secs(1));
+        assert_eq!(timer.0.now(), start + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn advance_clock_ignores_backwards_step() {
+        let start = Instant::now();
+        let timer = TimerRc(Rc::new(Timer::new(start)));
+        advance_clock(&timer.0, start + Duration::from_secs(1));
+        advance_clock(&timer.0, start);
+        assert_eq!(timer.0.now(), start + Duration::from_secs(1));
+    }
+}